@@ -0,0 +1,58 @@
+use std::process::Command;
+
+/// A command whose program+args differ by platform, so call sites stop
+/// hand-rolling `cfg!(windows)` branches around `Command::new`. The static
+/// `unix`/`windows` halves are trusted templates (split on whitespace);
+/// values that might contain spaces (URLs, paths) go through `arg()` so
+/// they're passed as a single argument rather than re-split.
+///
+/// Only covers the detached-launch case (`spawn`) used by `open_url`,
+/// `open_install_folder`, and `launch_proxy` today. The blocking
+/// run/run-with-output/working-directory variants were speculative and
+/// unused, so they aren't here - add them back (with real callers) if a
+/// future command needs to wait on or capture one of these platform-forked
+/// invocations.
+pub struct Cmd {
+    unix: &'static str,
+    windows: &'static str,
+    extra_args: Vec<String>,
+}
+
+impl Cmd {
+    pub fn new(unix: &'static str, windows: &'static str) -> Self {
+        Cmd { unix, windows, extra_args: Vec::new() }
+    }
+
+    pub fn arg(mut self, value: impl Into<String>) -> Self {
+        self.extra_args.push(value.into());
+        self
+    }
+
+    fn template(&self) -> &'static str {
+        if cfg!(windows) { self.windows } else { self.unix }
+    }
+
+    fn command_line(&self) -> String {
+        if self.extra_args.is_empty() {
+            self.template().to_string()
+        } else {
+            format!("{} {}", self.template(), self.extra_args.join(" "))
+        }
+    }
+
+    fn build(&self) -> Command {
+        let mut parts = self.template().split_whitespace();
+        let program = parts.next().unwrap_or_default();
+
+        let mut command = Command::new(program);
+        command.args(parts);
+        command.args(&self.extra_args);
+        command
+    }
+
+    /// Launches the command detached, without waiting for it to finish.
+    pub fn spawn(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.build().spawn().map_err(|e| format!("Failed to spawn `{}`: {}", self.command_line(), e))?;
+        Ok(())
+    }
+}
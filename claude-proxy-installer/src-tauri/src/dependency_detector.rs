@@ -1,9 +1,12 @@
+use std::path::Path;
 use std::process::Command;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum DependencyStatus {
     Found(String),   // Version string
+    /// Installed, but older than the minimum this proxy needs.
+    Outdated { found: String, required: String },
     NotFound,
 }
 
@@ -13,48 +16,93 @@ pub struct DetectionResults {
     pub nodejs: DependencyStatus,
     pub git: DependencyStatus,
     pub claude: DependencyStatus,
+    /// Directory the user pointed us at via `--install-dir`, bypassing all
+    /// the PATH/registry/npm heuristics below. Threaded through so later
+    /// install steps can honor it too.
+    #[serde(rename = "installDirOverride")]
+    pub install_dir_override: Option<String>,
 }
 
 
-pub async fn detect_python() -> DependencyStatus {
-    // Try python3 first (preferred on Unix)
-    if let Some(version) = check_command_version("python3", "--version").await {
-        if is_python_version_sufficient(&version) {
-            return DependencyStatus::Found(version);
-        }
-    }
+pub(crate) const PYTHON_MIN_VERSION: (u32, u32, u32) = (3, 8, 0);
+pub(crate) const NODEJS_MIN_VERSION: (u32, u32, u32) = (16, 0, 0);
+/// Git doesn't gate any feature this installer relies on, so this just rules
+/// out genuinely ancient installs rather than tracking a real compatibility
+/// floor.
+pub(crate) const GIT_MIN_VERSION: (u32, u32, u32) = (2, 0, 0);
 
-    // Try python (Windows default)
-    if let Some(version) = check_command_version("python", "--version").await {
-        if is_python_version_sufficient(&version) {
-            return DependencyStatus::Found(version);
+pub async fn detect_python() -> DependencyStatus {
+    // Try python3 first (preferred on Unix), falling back to python
+    // (Windows default). If the first command we find is too old we keep
+    // looking - a newer interpreter might be registered under the other name.
+    let mut outdated = None;
+
+    for cmd in ["python3", "python"] {
+        if let Some(version) = check_command_version(cmd, "--version").await {
+            if let Some(version_part) = version.split_whitespace().nth(1) {
+                match classify_version(&version, version_part, PYTHON_MIN_VERSION) {
+                    found @ DependencyStatus::Found(_) => return found,
+                    status @ DependencyStatus::Outdated { .. } => outdated.get_or_insert(status),
+                    DependencyStatus::NotFound => continue,
+                };
+            }
         }
     }
 
-    DependencyStatus::NotFound
+    outdated.unwrap_or(DependencyStatus::NotFound)
 }
 
 pub async fn detect_nodejs() -> DependencyStatus {
     if let Some(version) = check_command_version("node", "--version").await {
-        if is_nodejs_version_sufficient(&version) {
-            return DependencyStatus::Found(version);
-        }
+        return classify_version(&version, &version, NODEJS_MIN_VERSION);
     }
 
     DependencyStatus::NotFound
 }
 
 pub async fn detect_git() -> DependencyStatus {
+    // `git --version` prints e.g. "git version 2.42.0.windows.2" - the
+    // dotted version is always the third whitespace-separated token.
     if let Some(version) = check_command_version("git", "--version").await {
-        return DependencyStatus::Found(version);
+        if let Some(version_part) = version.split_whitespace().nth(2) {
+            return classify_version(&version, version_part, GIT_MIN_VERSION);
+        }
     }
 
     DependencyStatus::NotFound
 }
 
-pub async fn detect_claude() -> DependencyStatus {
+/// Unlike `detect_python`/`detect_nodejs`/`detect_git`, this never returns
+/// `Outdated` - most of its methods (registry lookup, `/Applications` scan,
+/// npm global list, common-path probing) only confirm presence and don't
+/// surface a comparable version string, so there's no reliable floor to
+/// parse against. `start_installation` still has to handle the `Outdated`
+/// arm for exhaustiveness, it's just unreachable for Claude Code today.
+pub async fn detect_claude(install_dir_override: Option<&Path>) -> DependencyStatus {
     println!("Starting comprehensive Claude Code detection...");
 
+    // Method 0: User gave us an explicit directory - trust it and skip every
+    // other heuristic. This is what CI and unusual layouts should use.
+    if let Some(dir) = install_dir_override {
+        println!("Using --install-dir override: {:?}", dir);
+        for candidate in ["claude.exe", "claude.cmd", "claude.bat", "claude"] {
+            let path = dir.join(candidate);
+            if path.exists() {
+                println!("✅ Found Claude Code at override dir: {:?}", path);
+                return DependencyStatus::Found("Claude Code (--install-dir)".to_string());
+            }
+        }
+        println!("❌ --install-dir override given but no claude executable found there");
+        return DependencyStatus::NotFound;
+    }
+
+    // Method 0b: System-wide install discovery (registry on Windows, the
+    // Applications folder / system_profiler on macOS) before the generic
+    // PATH/npm heuristics below.
+    if let Some(status) = detect_claude_system_install().await {
+        return status;
+    }
+
     // BULLETPROOF DETECTION: Try every possible method
 
     // Method 1: Standard command checks with comprehensive arguments
@@ -178,6 +226,85 @@ pub async fn detect_claude() -> DependencyStatus {
     DependencyStatus::NotFound
 }
 
+/// Looks for an existing Claude Code install registered with the OS rather
+/// than guessing well-known directories: Windows app-path / uninstall
+/// registry keys by app id, macOS `/Applications` (falling back to the slow
+/// `system_profiler` enumeration only when that misses). Linux has no such
+/// registry, so it keeps using the PATH scan below.
+#[cfg(target_os = "windows")]
+async fn detect_claude_system_install() -> Option<DependencyStatus> {
+    let powershell_script = r#"
+        $roots = @(
+            'HKCU:\Software\Microsoft\Windows\CurrentVersion\App Paths\claude.exe',
+            'HKLM:\Software\Microsoft\Windows\CurrentVersion\App Paths\claude.exe',
+            'HKCU:\Software\Microsoft\Windows\CurrentVersion\Uninstall\Claude Code',
+            'HKLM:\Software\Microsoft\Windows\CurrentVersion\Uninstall\Claude Code'
+        )
+        foreach ($root in $roots) {
+            if (Test-Path $root) {
+                $value = (Get-ItemProperty -Path $root -ErrorAction SilentlyContinue).'(default)'
+                if ($value) {
+                    Write-Output "FOUND:$value"
+                    exit 0
+                }
+            }
+        }
+        Write-Output 'NOT_FOUND'
+    "#;
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-Command", powershell_script])
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let result = stdout.trim();
+
+    if let Some(path) = result.strip_prefix("FOUND:") {
+        println!("✅ Found Claude Code via Windows registry: {}", path);
+        return Some(DependencyStatus::Found("Claude Code (registry)".to_string()));
+    }
+
+    None
+}
+
+#[cfg(target_os = "macos")]
+async fn detect_claude_system_install() -> Option<DependencyStatus> {
+    // Fast path: the app bundle usually lives directly under /Applications.
+    let applications = std::path::Path::new("/Applications");
+    if let Ok(entries) = std::fs::read_dir(applications) {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy().to_lowercase();
+            if name.contains("claude") {
+                println!("✅ Found Claude Code in /Applications: {:?}", entry.path());
+                return Some(DependencyStatus::Found("Claude Code (/Applications)".to_string()));
+            }
+        }
+    }
+
+    // Slow fallback: ask the system profiler, same technique macOS support
+    // tooling uses when a quick directory scan doesn't find the app.
+    if let Ok(output) = Command::new("system_profiler")
+        .arg("SPApplicationsDataType")
+        .output()
+    {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.to_lowercase().contains("claude") {
+            println!("✅ Found Claude Code via system_profiler");
+            return Some(DependencyStatus::Found("Claude Code (system_profiler)".to_string()));
+        }
+    }
+
+    None
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+async fn detect_claude_system_install() -> Option<DependencyStatus> {
+    // Linux has no equivalent system registry - the PATH scan below covers it.
+    None
+}
+
 async fn check_command_version(command: &str, version_arg: &str) -> Option<String> {
     println!("Checking for command: {}", command);
 
@@ -209,29 +336,36 @@ async fn check_command_version(command: &str, version_arg: &str) -> Option<Strin
     }
 }
 
-fn is_python_version_sufficient(version_string: &str) -> bool {
-    // Parse "Python 3.11.0" or similar format
-    if let Some(version_part) = version_string.split_whitespace().nth(1) {
-        if let Some((major, rest)) = version_part.split_once('.') {
-            if let Some((minor, _)) = rest.split_once('.') {
-                if let (Ok(maj), Ok(min)) = (major.parse::<u32>(), minor.parse::<u32>()) {
-                    return maj > 3 || (maj == 3 && min >= 8);
-                }
-            }
-        }
+/// Parses a dotted version string into a comparable `(major, minor, patch)`
+/// tuple: strips a leading `v`, and ignores anything after the first
+/// non-digit character in each segment (pre-release/build suffixes like
+/// `3.12.0rc1` or `18.18.2-nightly`).
+pub(crate) fn parse_version_tuple(version_part: &str) -> Option<(u32, u32, u32)> {
+    fn leading_digits(segment: &str) -> Option<u32> {
+        let digits: String = segment.chars().take_while(|c| c.is_ascii_digit()).collect();
+        if digits.is_empty() { None } else { digits.parse().ok() }
     }
-    false
+
+    let cleaned = version_part.trim_start_matches('v');
+    let mut parts = cleaned.splitn(3, '.');
+    let major = leading_digits(parts.next()?)?;
+    let minor = parts.next().and_then(leading_digits).unwrap_or(0);
+    let patch = parts.next().and_then(leading_digits).unwrap_or(0);
+    Some((major, minor, patch))
 }
 
-fn is_nodejs_version_sufficient(version_string: &str) -> bool {
-    // Parse "v18.17.0" or similar format
-    let version_clean = version_string.trim_start_matches('v');
-    if let Some((major, _)) = version_clean.split_once('.') {
-        if let Ok(maj) = major.parse::<u32>() {
-            return maj >= 16; // Node.js 16+ is sufficient
-        }
+/// Classifies a detected version against a minimum requirement. `full_label`
+/// is what gets stored/displayed (e.g. `"Python 3.8.0"`), `version_part` is
+/// just the dotted number to parse (e.g. `"3.8.0"`).
+fn classify_version(full_label: &str, version_part: &str, required: (u32, u32, u32)) -> DependencyStatus {
+    match parse_version_tuple(version_part) {
+        Some(found) if found >= required => DependencyStatus::Found(full_label.to_string()),
+        Some(_) => DependencyStatus::Outdated {
+            found: full_label.to_string(),
+            required: format!("{}.{}.{}", required.0, required.1, required.2),
+        },
+        None => DependencyStatus::NotFound,
     }
-    false
 }
 
 #[cfg(test)]
@@ -240,16 +374,33 @@ mod tests {
 
     #[test]
     fn test_python_version_parsing() {
-        assert!(is_python_version_sufficient("Python 3.8.0"));
-        assert!(is_python_version_sufficient("Python 3.11.5"));
-        assert!(!is_python_version_sufficient("Python 3.7.9"));
-        assert!(!is_python_version_sufficient("Python 2.7.18"));
+        assert!(matches!(classify_version("Python 3.8.0", "3.8.0", PYTHON_MIN_VERSION), DependencyStatus::Found(_)));
+        assert!(matches!(classify_version("Python 3.11.5", "3.11.5", PYTHON_MIN_VERSION), DependencyStatus::Found(_)));
+        assert!(matches!(classify_version("Python 3.7.9", "3.7.9", PYTHON_MIN_VERSION), DependencyStatus::Outdated { .. }));
+        assert!(matches!(classify_version("Python 2.7.18", "2.7.18", PYTHON_MIN_VERSION), DependencyStatus::Outdated { .. }));
     }
 
     #[test]
     fn test_nodejs_version_parsing() {
-        assert!(is_nodejs_version_sufficient("v18.17.0"));
-        assert!(is_nodejs_version_sufficient("v16.0.0"));
-        assert!(!is_nodejs_version_sufficient("v14.21.3"));
+        assert!(matches!(classify_version("v18.17.0", "v18.17.0", NODEJS_MIN_VERSION), DependencyStatus::Found(_)));
+        assert!(matches!(classify_version("v16.0.0", "v16.0.0", NODEJS_MIN_VERSION), DependencyStatus::Found(_)));
+        assert!(matches!(classify_version("v14.21.3", "v14.21.3", NODEJS_MIN_VERSION), DependencyStatus::Outdated { .. }));
+    }
+
+    #[test]
+    fn test_version_parsing_ignores_prerelease_suffix() {
+        assert_eq!(parse_version_tuple("3.12.0rc1"), Some((3, 12, 0)));
+        assert_eq!(parse_version_tuple("v18.18.2-nightly"), Some((18, 18, 2)));
+    }
+
+    #[test]
+    fn test_git_version_parsing() {
+        let full = "git version 2.42.0.windows.2";
+        let version_part = full.split_whitespace().nth(2).unwrap();
+        assert!(matches!(classify_version(full, version_part, GIT_MIN_VERSION), DependencyStatus::Found(_)));
+
+        let full = "git version 1.9.5";
+        let version_part = full.split_whitespace().nth(2).unwrap();
+        assert!(matches!(classify_version(full, version_part, GIT_MIN_VERSION), DependencyStatus::Outdated { .. }));
     }
 }
\ No newline at end of file
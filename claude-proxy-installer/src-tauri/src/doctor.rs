@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::dependency_detector::{self, DependencyStatus};
+use crate::installer;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScriptInfo {
+    pub name: String,
+    pub sha256: String,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub python: DependencyStatus,
+    pub nodejs: DependencyStatus,
+    pub git: DependencyStatus,
+    pub claude: DependencyStatus,
+    #[serde(rename = "installDir")]
+    pub install_dir: String,
+    #[serde(rename = "installDirOnPath")]
+    pub install_dir_on_path: bool,
+    #[serde(rename = "npmPrefix")]
+    pub npm_prefix: Option<String>,
+    #[serde(rename = "nodeAbi")]
+    pub node_abi: Option<String>,
+    #[serde(rename = "proxyScripts")]
+    pub proxy_scripts: Vec<ScriptInfo>,
+}
+
+/// Builds the full environment snapshot shown by the doctor/info command:
+/// dependency statuses, whether the install directory is actually on PATH,
+/// the resolved npm prefix/Node ABI, and a SHA-256 + byte-size fingerprint of
+/// every embedded proxy script so bug reports capture exactly what was
+/// installed. No per-script version is recorded.
+pub async fn build_report() -> Result<DoctorReport, Box<dyn std::error::Error>> {
+    let install_dir = installer::get_install_directory()?;
+
+    let proxy_scripts = installer::embedded_proxy_scripts()
+        .into_iter()
+        .map(|(name, content)| ScriptInfo {
+            name: name.to_string(),
+            sha256: format!("{:x}", Sha256::digest(content.as_bytes())),
+            bytes: content.len(),
+        })
+        .collect();
+
+    Ok(DoctorReport {
+        python: dependency_detector::detect_python().await,
+        nodejs: dependency_detector::detect_nodejs().await,
+        git: dependency_detector::detect_git().await,
+        claude: dependency_detector::detect_claude(None).await,
+        install_dir_on_path: installer::is_in_path(&install_dir).unwrap_or(false),
+        install_dir: install_dir.to_string_lossy().to_string(),
+        npm_prefix: resolve_npm_prefix(),
+        node_abi: resolve_node_abi(),
+        proxy_scripts,
+    })
+}
+
+fn resolve_npm_prefix() -> Option<String> {
+    let output = std::process::Command::new("npm").args(["config", "get", "prefix"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if prefix.is_empty() { None } else { Some(prefix) }
+}
+
+fn resolve_node_abi() -> Option<String> {
+    let output = std::process::Command::new("node").args(["-e", "console.log(process.versions.modules)"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let abi = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if abi.is_empty() { None } else { Some(abi) }
+}
+
+/// Renders the report as the colorized table shown in the human-readable
+/// (non `--json`) form of the doctor command.
+pub fn render_table(report: &DoctorReport) -> String {
+    fn status_line(label: &str, status: &DependencyStatus) -> String {
+        match status {
+            DependencyStatus::Found(version) => format!("  {:<10} \x1b[32m✓ {}\x1b[0m", label, version),
+            DependencyStatus::Outdated { found, required } => format!("  {:<10} \x1b[33m⚠ {} (need ≥{})\x1b[0m", label, found, required),
+            DependencyStatus::NotFound => format!("  {:<10} \x1b[31m✗ not found\x1b[0m", label),
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str("Claude Proxy Doctor Report\n");
+    out.push_str(&status_line("Python", &report.python));
+    out.push('\n');
+    out.push_str(&status_line("Node.js", &report.nodejs));
+    out.push('\n');
+    out.push_str(&status_line("Git", &report.git));
+    out.push('\n');
+    out.push_str(&status_line("Claude", &report.claude));
+    out.push('\n');
+    out.push_str(&format!("  Install dir: {} (on PATH: {})\n", report.install_dir, report.install_dir_on_path));
+    out.push_str(&format!("  npm prefix:  {}\n", report.npm_prefix.as_deref().unwrap_or("unknown")));
+    out.push_str(&format!("  Node ABI:    {}\n", report.node_abi.as_deref().unwrap_or("unknown")));
+    out.push_str("  Proxy scripts:\n");
+    for script in &report.proxy_scripts {
+        out.push_str(&format!("    {:<32} {} ({} bytes)\n", script.name, &script.sha256[..12], script.bytes));
+    }
+    out
+}
@@ -1,13 +1,406 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::io::Write;
+use std::time::Duration;
+use futures_util::StreamExt;
 use reqwest;
 
+/// How many attempts `download_file` makes in total (the first try plus
+/// retries) before giving up, with exponential backoff between attempts.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 4;
+
 const PYTHON_WINDOWS_URL: &str = "https://github.com/indygreg/python-build-standalone/releases/download/20231002/cpython-3.11.6+20231002-x86_64-pc-windows-msvc-shared-install_only.tar.gz";
+const PYTHON_WINDOWS_RELEASE_TAG: &str = "20231002";
+const NODEJS_WINDOWS_VERSION_DIR: &str = "v18.18.2";
+const NODEJS_WINDOWS_ARCHIVE: &str = "node-v18.18.2-win-x64.zip";
 const NODEJS_WINDOWS_URL: &str = "https://nodejs.org/dist/v18.18.2/node-v18.18.2-win-x64.zip";
 const GIT_WINDOWS_URL: &str = "https://github.com/git-for-windows/git/releases/download/v2.42.0.windows.2/PortableGit-2.42.0.2-64-bit.7z.exe";
+const GIT_WINDOWS_RELEASE_TAG: &str = "v2.42.0.windows.2";
+
+// Pinned LTS major that still satisfies `is_nodejs_version_sufficient` (>=16).
+const NODEJS_BOOTSTRAP_VERSION: &str = "20.11.1";
+const NODEJS_DIST_BASE: &str = "https://nodejs.org/dist";
+
+/// Platform path separator used when composing PATH entries by hand.
+pub const PATH_SEPARATOR: &str = if cfg!(windows) { ";" } else { ":" };
+
+/// Downloads and extracts a standalone Node.js distribution when the system
+/// has none, so `install_claude_cli` is never stuck because npm is missing.
+/// Verifies the archive against the official `SHASUMS256.txt` before
+/// trusting it, the same check `download_nodejs` runs on its Windows
+/// archive.
+pub async fn bootstrap_nodejs() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    println!("Bootstrapping Node.js {} (none found on PATH)...", NODEJS_BOOTSTRAP_VERSION);
+
+    let install_dir = get_install_directory()?;
+    let nodejs_dir = install_dir.join("nodejs-bootstrap");
+
+    let (archive_name, is_zip) = nodejs_archive_name();
+    let version_dir = format!("v{}", NODEJS_BOOTSTRAP_VERSION);
+    let url = format!("{}/{}/{}", NODEJS_DIST_BASE, version_dir, archive_name);
+    let download_path = install_dir.join(&archive_name);
+
+    download_file(&url, &download_path).await?;
+    verify_against_shasums(&version_dir, &archive_name, &download_path).await?;
+
+    if is_zip {
+        extract_zip(&download_path, &nodejs_dir)?;
+    } else {
+        extract_archive(&download_path, &nodejs_dir)?;
+    }
+
+    // The Windows zip is extracted as-is, so it keeps its single top-level
+    // `node-v<version>-<platform>` folder; the Unix tar.gz is extracted via
+    // `extract_archive`, which strips that same top-level folder, so its
+    // contents already land directly under `nodejs_dir`.
+    let bin_dir = if cfg!(windows) {
+        nodejs_dir.join(archive_name.trim_end_matches(".zip"))
+    } else {
+        nodejs_dir.join("bin")
+    };
+
+    crate::installer::add_to_path(&bin_dir)?;
+
+    println!("Node.js bootstrapped to: {:?}", bin_dir);
+    Ok(bin_dir)
+}
+
+fn nodejs_archive_name() -> (String, bool) {
+    let version = NODEJS_BOOTSTRAP_VERSION;
+    if cfg!(target_os = "windows") {
+        (format!("node-v{}-win-x64.zip", version), true)
+    } else if cfg!(target_os = "macos") {
+        let arch = if cfg!(target_arch = "aarch64") { "arm64" } else { "x64" };
+        (format!("node-v{}-darwin-{}.tar.gz", version, arch), false)
+    } else {
+        let arch = if cfg!(target_arch = "aarch64") { "arm64" } else { "x64" };
+        (format!("node-v{}-linux-{}.tar.gz", version, arch), false)
+    }
+}
+
+async fn verify_against_shasums(version_dir: &str, archive_name: &str, archive_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let shasums_url = format!("{}/{}/SHASUMS256.txt", NODEJS_DIST_BASE, version_dir);
+    let shasums = reqwest::get(&shasums_url).await?.text().await?;
+
+    let expected = shasums
+        .lines()
+        .find(|line| line.ends_with(archive_name))
+        .and_then(|line| line.split_whitespace().next())
+        .ok_or_else(|| format!("No checksum entry for {} in SHASUMS256.txt", archive_name))?;
+
+    let actual = sha256_hex(archive_path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            archive_name, expected, actual
+        ).into());
+    }
+
+    println!("Verified {} against SHASUMS256.txt", archive_name);
+    Ok(())
+}
+
+fn sha256_hex(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Verifies a downloaded archive against its known digest before anything
+/// extracts it - the Windows counterpart to `verify_against_shasums` for
+/// GitHub-hosted archives, whose digest `fetch_release_digest` has already
+/// pulled out of the release.
+fn verify_checksum(path: &Path, label: &str, expected_sha256: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let actual = sha256_hex(path)?;
+    if !actual.eq_ignore_ascii_case(expected_sha256) {
+        return Err(format!(
+            "Checksum mismatch for {}: expected {}, got {}. The download may be corrupted or tampered with.",
+            label, expected_sha256, actual
+        ).into());
+    }
+
+    println!("Verified {} checksum", label);
+    Ok(())
+}
+
+#[derive(serde::Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+#[derive(serde::Deserialize)]
+struct GithubRelease {
+    assets: Vec<GithubAsset>,
+    body: Option<String>,
+}
+
+async fn fetch_release(releases_url: &str) -> Result<GithubRelease, Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+    let release: GithubRelease = client
+        .get(releases_url)
+        .header(reqwest::header::USER_AGENT, "claude-proxy-installer")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(release)
+}
+
+/// Finds the newest release asset on GitHub whose name ends with
+/// `name_suffix`, optionally narrowed to a `requested_version` range.
+async fn fetch_latest_asset_url(releases_url: &str, name_suffix: &str, requested_version: Option<&str>) -> Result<String, Box<dyn std::error::Error>> {
+    let release = fetch_release(releases_url).await?;
+    pick_release_asset(&release, name_suffix, requested_version)
+        .map(|asset| asset.browser_download_url.clone())
+        .ok_or_else(|| format!("No release asset matching *{}", name_suffix).into())
+}
+
+/// Pulls the dotted version out of a release asset name - the `3.11.6` in
+/// `cpython-3.11.6+20231002-x86_64-pc-windows-msvc-shared-install_only.tar.gz`,
+/// or the `2.42.0.2` in `PortableGit-2.42.0.2-64-bit.7z.exe` - by taking the
+/// first run of digit/dot characters that contains at least two dots, so an
+/// unversioned numeric run elsewhere in the name (a build date, an
+/// architecture suffix) never matches instead.
+fn asset_version(name: &str) -> Option<(u32, u32, u32)> {
+    name.split(|c: char| !c.is_ascii_digit() && c != '.')
+        .find(|token| token.matches('.').count() >= 2)
+        .and_then(crate::dependency_detector::parse_version_tuple)
+}
+
+/// Whether `version` satisfies a requested version/range string: `"3.11"` or
+/// `"3"` matches any version sharing that major(.minor) prefix, an exact
+/// `"3.11.6"` matches only that version, and `None` imposes no constraint.
+fn version_matches(version: (u32, u32, u32), requested: Option<&str>) -> bool {
+    let Some(requested) = requested else { return true };
+    let requested = requested.trim_start_matches('v');
+    let mut parts = requested.splitn(3, '.').map(|part| part.parse::<u32>().ok());
+
+    match (parts.next().flatten(), parts.next().flatten(), parts.next().flatten()) {
+        (Some(major), None, _) => version.0 == major,
+        (Some(major), Some(minor), None) => version.0 == major && version.1 == minor,
+        (Some(major), Some(minor), Some(patch)) => version == (major, minor, patch),
+        _ => true,
+    }
+}
+
+/// Picks the newest release asset matching `asset_suffix` (and, if given, the
+/// `requested_version` range). A single "latest" release often bundles every
+/// supported minor under the same suffix - every CPython 3.9-3.13
+/// `install_only` build, for python-build-standalone - so picking the first
+/// suffix match picks whichever GitHub happens to list first, not the
+/// newest. An asset whose name has no parseable version is only considered
+/// when nothing is being requested (there's no version to rank it by).
+fn pick_release_asset<'a>(release: &'a GithubRelease, asset_suffix: &str, requested_version: Option<&str>) -> Option<&'a GithubAsset> {
+    release.assets.iter()
+        .filter(|asset| asset.name.ends_with(asset_suffix))
+        .filter(|asset| match asset_version(&asset.name) {
+            Some(version) => version_matches(version, requested_version),
+            None => requested_version.is_none(),
+        })
+        .max_by_key(|asset| asset_version(&asset.name).unwrap_or((0, 0, 0)))
+}
+
+/// Finds the published SHA-256 digest for `asset_name` in `release`, trying
+/// every convention the two GitHub-hosted dependencies actually use: a
+/// `<asset_name>.sha256` companion asset (how python-build-standalone
+/// publishes per-build digests), a combined `SHA256SUMS`/`sha256sums.txt`
+/// asset, and finally the release notes body, which is where git-for-windows
+/// lists its per-asset hashes instead. Returns `None` - never a guessed
+/// value - when none of these sources yields a match.
+async fn fetch_release_digest(release: &GithubRelease, asset_name: &str) -> Option<String> {
+    let companion_name = format!("{}.sha256", asset_name);
+    if let Some(asset) = release.assets.iter().find(|asset| asset.name == companion_name) {
+        if let Some(digest) = fetch_text(&asset.browser_download_url).await
+            .and_then(|text| first_hex64_token(&text))
+        {
+            return Some(digest);
+        }
+    }
+
+    for asset in &release.assets {
+        if asset.name == asset_name || !asset.name.to_lowercase().contains("sha256") {
+            continue;
+        }
+        if let Some(text) = fetch_text(&asset.browser_download_url).await {
+            if let Some(digest) = find_sha256_for_name(&text, asset_name) {
+                return Some(digest);
+            }
+        }
+    }
+
+    release.body.as_deref().and_then(|body| find_sha256_for_name(body, asset_name))
+}
+
+async fn fetch_text(url: &str) -> Option<String> {
+    reqwest::get(url).await.ok()?.text().await.ok()
+}
+
+/// Pulls the first standalone 64-character hex token out of `text` - used for
+/// `<asset>.sha256` companion files, which contain nothing but the digest
+/// (optionally followed by the filename, as `sha256sum` itself would emit).
+fn first_hex64_token(text: &str) -> Option<String> {
+    text.split(|c: char| !c.is_ascii_hexdigit())
+        .find(|token| token.len() == 64)
+        .map(|token| token.to_lowercase())
+}
+
+/// Picks the 64-character hex digest out of a checksums-file or release-notes
+/// line that mentions `asset_name` - handles the classic `<hash>  <filename>`
+/// layout as well as release-notes tables/backtick-quoted filenames, since
+/// splitting on anything that isn't a hex digit isolates the hash either way.
+fn find_sha256_for_name(text: &str, asset_name: &str) -> Option<String> {
+    text.lines()
+        .filter(|line| line.contains(asset_name))
+        .find_map(|line| {
+            line.split(|c: char| !c.is_ascii_hexdigit())
+                .find(|token| token.len() == 64)
+                .map(|token| token.to_lowercase())
+        })
+}
+
+/// Resolves a GitHub-hosted Windows build to a download URL plus its
+/// published SHA-256 digest, preferring the newest release and falling back
+/// to the exact release tag this binary was pinned against when the
+/// "latest" lookup fails or its asset shape changes - so a network hiccup
+/// never blocks install, it just pins to the last known-good build. Returns
+/// `None` for the digest when the resolved release publishes no digest for
+/// the asset, or when no release lookup yields a matching asset and the
+/// pinned fallback URL is used; a missing digest is never replaced with a guess.
+/// `requested_version` narrows asset selection to a major[.minor[.patch]]
+/// range (e.g. `"3.11"`); `None` just picks the newest matching asset.
+async fn resolve_github_release(
+    label: &str,
+    latest_releases_url: &str,
+    pinned_release_url: &str,
+    asset_suffix: &str,
+    requested_version: Option<&str>,
+    pinned_fallback_url: &str,
+) -> (String, Option<String>) {
+    for (releases_url, origin) in [(latest_releases_url, "latest"), (pinned_release_url, "pinned")] {
+        match fetch_release(releases_url).await {
+            Ok(release) => {
+                match pick_release_asset(&release, asset_suffix, requested_version) {
+                    Some(asset) => {
+                        let url = asset.browser_download_url.clone();
+                        let name = asset.name.clone();
+                        println!("Resolved {} release ({}): {}", label, origin, url);
+
+                        let digest = fetch_release_digest(&release, &name).await;
+                        if digest.is_none() {
+                            println!("No published checksum found for {} in the {} release notes", name, label);
+                        }
+                        return (url, digest);
+                    }
+                    None => println!(
+                        "{} {} release has no asset matching *{}{}",
+                        label, origin, asset_suffix,
+                        requested_version.map(|v| format!(" for version {}", v)).unwrap_or_default()
+                    ),
+                }
+            }
+            Err(e) => println!("Failed to query {} {} release: {}", label, origin, e),
+        }
+    }
+
+    println!("Falling back to last known-good {} build ({}) with no checksum verification (no release lookup yielded a matching asset)", label, pinned_fallback_url);
+    (pinned_fallback_url.to_string(), None)
+}
+
+/// Resolves the python-build-standalone Windows build from GitHub releases
+/// instead of always fetching the version pinned at build time. `requested_version`
+/// (e.g. `"3.11"` or an exact `"3.11.6"`) narrows which CPython minor gets
+/// picked out of a release that bundles every supported one; `None` picks
+/// the newest.
+async fn resolve_python_release(requested_version: Option<&str>) -> (String, Option<String>) {
+    resolve_github_release(
+        "python-build-standalone",
+        "https://api.github.com/repos/indygreg/python-build-standalone/releases/latest",
+        &format!("https://api.github.com/repos/indygreg/python-build-standalone/releases/tags/{}", PYTHON_WINDOWS_RELEASE_TAG),
+        "x86_64-pc-windows-msvc-shared-install_only.tar.gz",
+        requested_version,
+        PYTHON_WINDOWS_URL,
+    ).await
+}
+
+/// Same idea as `resolve_python_release`, for Git for Windows.
+async fn resolve_git_release(requested_version: Option<&str>) -> (String, Option<String>) {
+    resolve_github_release(
+        "Git for Windows",
+        "https://api.github.com/repos/git-for-windows/git/releases/latest",
+        &format!("https://api.github.com/repos/git-for-windows/git/releases/tags/{}", GIT_WINDOWS_RELEASE_TAG),
+        "64-bit.7z.exe",
+        requested_version,
+        GIT_WINDOWS_URL,
+    ).await
+}
+
+/// Runs `path --version` and checks the first dotted-version token in its
+/// output against `required`, reusing `dependency_detector`'s parser so a
+/// reused system install is held to the same floor `detect_python`/
+/// `detect_nodejs` already enforce for a PATH-based one. Treats a failure to
+/// run the binary, or an unparsable/too-old version, as "doesn't qualify".
+fn meets_min_version(path: &Path, required: (u32, u32, u32)) -> bool {
+    let output = match std::process::Command::new(path).arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        _ => return false,
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .find_map(crate::dependency_detector::parse_version_tuple)
+        .map(|found| found >= required)
+        .unwrap_or(false)
+}
+
+/// Probes for a system-wide install that `start_installation` could reuse
+/// instead of downloading a private copy. Independent of `detect_python`'s
+/// PATH-based check since the Windows `py` launcher can list interpreters
+/// that were never added to PATH under the plain `python`/`python3` name.
+/// Only returns a candidate whose `--version` meets `PYTHON_MIN_VERSION` -
+/// an ancient interpreter on PATH is treated the same as none at all.
+pub fn find_existing_python() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        let output = std::process::Command::new("py").arg("-0p").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_whitespace().last().map(PathBuf::from))
+            .find(|path| meets_min_version(path, crate::dependency_detector::PYTHON_MIN_VERSION))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        [which::which("python3"), which::which("python")]
+            .into_iter()
+            .filter_map(|result| result.ok())
+            .find(|path| meets_min_version(path, crate::dependency_detector::PYTHON_MIN_VERSION))
+    }
+}
+
+/// Same idea as `find_existing_python`, but Node.js/Git don't have a launcher
+/// analogous to `py` - `which`/`where` (which the `which` crate dispatches
+/// to per-platform) is the whole story. Still version-gated, so an outdated
+/// install on PATH doesn't get silently reused.
+pub fn find_existing_nodejs() -> Option<PathBuf> {
+    which::which("node").ok().filter(|path| meets_min_version(path, crate::dependency_detector::NODEJS_MIN_VERSION))
+}
 
-pub async fn download_python() -> Result<PathBuf, Box<dyn std::error::Error>> {
+pub fn find_existing_git() -> Option<PathBuf> {
+    which::which("git").ok().filter(|path| meets_min_version(path, crate::dependency_detector::GIT_MIN_VERSION))
+}
+
+/// Downloads and installs Python. `requested_version` optionally narrows the
+/// resolved build to a major[.minor[.patch]] range (e.g. `"3.11"`); `None`
+/// picks the newest available build.
+pub async fn download_python(requested_version: Option<&str>) -> Result<PathBuf, Box<dyn std::error::Error>> {
     println!("Downloading Python...");
 
     let install_dir = get_install_directory()?;
@@ -15,11 +408,15 @@ pub async fn download_python() -> Result<PathBuf, Box<dyn std::error::Error>> {
 
     #[cfg(target_os = "windows")]
     {
-        let url = PYTHON_WINDOWS_URL;
+        let (url, pinned_sha256) = resolve_python_release(requested_version).await;
         let file_name = "python.tar.gz";
         let download_path = install_dir.join(file_name);
 
-        download_file(url, &download_path).await?;
+        download_file(&url, &download_path).await?;
+        match pinned_sha256 {
+            Some(expected) => verify_checksum(&download_path, file_name, &expected)?,
+            None => println!("Proceeding without checksum verification: no published digest could be fetched"),
+        }
         extract_archive(&download_path, &python_dir)?;
 
         // Add to PATH
@@ -33,7 +430,7 @@ pub async fn download_python() -> Result<PathBuf, Box<dyn std::error::Error>> {
     #[cfg(not(target_os = "windows"))]
     {
         // For Unix systems, use system package manager or pyenv
-        install_python_unix().await
+        install_python_unix(requested_version).await
     }
 }
 
@@ -50,6 +447,7 @@ pub async fn download_nodejs() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let download_path = install_dir.join(file_name);
 
         download_file(url, &download_path).await?;
+        verify_against_shasums(NODEJS_WINDOWS_VERSION_DIR, NODEJS_WINDOWS_ARCHIVE, &download_path).await?;
         extract_zip(&download_path, &nodejs_dir)?;
 
         // Add to PATH
@@ -65,7 +463,11 @@ pub async fn download_nodejs() -> Result<PathBuf, Box<dyn std::error::Error>> {
     }
 }
 
-pub async fn download_git() -> Result<PathBuf, Box<dyn std::error::Error>> {
+/// Downloads and installs Git. `requested_version` optionally narrows the
+/// resolved Git-for-Windows build the same way `download_python` narrows its
+/// CPython build; the Unix package-manager path has no equivalent knob, so
+/// it always installs whatever the distro currently carries.
+pub async fn download_git(requested_version: Option<&str>) -> Result<PathBuf, Box<dyn std::error::Error>> {
     println!("Downloading Git...");
 
     let install_dir = get_install_directory()?;
@@ -73,11 +475,15 @@ pub async fn download_git() -> Result<PathBuf, Box<dyn std::error::Error>> {
 
     #[cfg(target_os = "windows")]
     {
-        let url = GIT_WINDOWS_URL;
+        let (url, pinned_sha256) = resolve_git_release(requested_version).await;
         let file_name = "git-portable.exe";
         let download_path = install_dir.join(file_name);
 
-        download_file(url, &download_path).await?;
+        download_file(&url, &download_path).await?;
+        match pinned_sha256 {
+            Some(expected) => verify_checksum(&download_path, file_name, &expected)?,
+            None => println!("Proceeding without checksum verification: no published digest could be fetched"),
+        }
 
         // PortableGit is self-extracting
         std::process::Command::new(&download_path)
@@ -95,23 +501,90 @@ pub async fn download_git() -> Result<PathBuf, Box<dyn std::error::Error>> {
 
     #[cfg(not(target_os = "windows"))]
     {
+        if let Some(version) = requested_version {
+            println!("Ignoring requested Git version {}: package-manager installs always take the distro's current version", version);
+        }
         install_git_unix().await
     }
 }
 
+/// Downloads `url` to `dest_path`, streaming to disk instead of buffering
+/// the whole response, logging coarse progress, resuming via HTTP Range if
+/// a previous attempt left a partial file behind, and retrying transient
+/// failures with exponential backoff.
 async fn download_file(url: &str, dest_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     println!("Downloading from: {}", url);
     println!("Saving to: {:?}", dest_path);
 
-    // Create parent directory if it doesn't exist
     if let Some(parent) = dest_path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let response = reqwest::get(url).await?;
-    let mut file = fs::File::create(dest_path)?;
-    let content = response.bytes().await?;
-    file.write_all(&content)?;
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match try_download_file(url, dest_path).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                let backoff = Duration::from_secs(2u64.pow(attempt - 1));
+                println!("Download attempt {} of {} failed ({}); retrying in {:?}...", attempt, MAX_DOWNLOAD_ATTEMPTS, e, backoff);
+                tokio::time::sleep(backoff).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+async fn try_download_file(url: &str, dest_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut existing_bytes = fs::metadata(dest_path).map(|m| m.len()).unwrap_or(0);
+
+    let client = reqwest::Client::new();
+    let mut request = client.get(url);
+    if existing_bytes > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", existing_bytes));
+    }
+
+    let mut response = request.send().await?;
+
+    // A `Range` request past the end of the file (typically: `dest_path`
+    // already holds a complete download from a prior run) gets a 416 rather
+    // than the 206 we asked for. Treat that as "start over" instead of
+    // letting `error_for_status` turn it into a retry that fails the same
+    // way four times in a row.
+    if existing_bytes > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        println!("Existing download at {:?} is already complete or stale; restarting from scratch", dest_path);
+        fs::remove_file(dest_path)?;
+        existing_bytes = 0;
+        response = client.get(url).send().await?;
+    }
+
+    let response = response.error_for_status()?;
+    let resumed = existing_bytes > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let total_bytes = response.content_length().map(|len| if resumed { len + existing_bytes } else { len });
+
+    let mut file = if resumed {
+        fs::OpenOptions::new().append(true).open(dest_path)?
+    } else {
+        fs::File::create(dest_path)?
+    };
+
+    let mut downloaded = if resumed { existing_bytes } else { 0 };
+    let mut last_logged_percent = u64::MAX;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk)?;
+        downloaded += chunk.len() as u64;
+
+        if let Some(total_bytes) = total_bytes {
+            let percent = downloaded.saturating_mul(100) / total_bytes.max(1);
+            if percent != last_logged_percent {
+                println!("Downloading {}: {}% ({}/{} bytes)", url, percent, downloaded, total_bytes);
+                last_logged_percent = percent;
+            }
+        }
+    }
 
     println!("Download completed: {:?}", dest_path);
     Ok(())
@@ -149,128 +622,181 @@ fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<(), Box<dyn std::er
     Ok(())
 }
 
+/// Extracts a `.tar.gz` in pure Rust - no more shelling out to a system
+/// `tar` that may or may not exist (Windows only gained one in build 17063).
+/// These archives (python-build-standalone, Node.js) wrap everything in a
+/// single top-level directory, so that component is stripped on the way out
+/// and the contents land directly under `extract_to`. `tar`'s unpacking
+/// already restores Unix file modes (executable bits on `bin/python3`, etc.)
+/// from the archive's recorded permissions.
 fn extract_archive(archive_path: &Path, extract_to: &Path) -> Result<(), Box<dyn std::error::Error>> {
     println!("Extracting archive: {:?} to {:?}", archive_path, extract_to);
 
     fs::create_dir_all(extract_to)?;
 
-    // For now, assume it's a tar.gz and use system tar if available
-    #[cfg(target_os = "windows")]
-    {
-        // Windows 10+ has built-in tar
-        let status = std::process::Command::new("tar")
-            .args(["-xzf", archive_path.to_str().unwrap()])
-            .args(["-C", extract_to.to_str().unwrap()])
-            .status();
-
-        match status {
-            Ok(status) if status.success() => {
-                println!("Archive extracted successfully");
-                Ok(())
-            }
-            _ => {
-                // Fallback: try to extract manually or use 7zip if available
-                Err("Failed to extract archive".into())
-            }
-        }
-    }
+    let file = fs::File::open(archive_path)?;
+    let decoder = flate2::read::GzDecoder::new(file);
+    let mut archive = tar::Archive::new(decoder);
+    archive.set_preserve_permissions(true);
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        let status = std::process::Command::new("tar")
-            .args(["-xzf", archive_path.to_str().unwrap()])
-            .args(["-C", extract_to.to_str().unwrap()])
-            .status()?;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
 
-        if status.success() {
-            println!("Archive extracted successfully");
-            Ok(())
-        } else {
-            Err("Failed to extract archive".into())
+        let relative: PathBuf = path.components().skip(1).collect();
+        if relative.as_os_str().is_empty() {
+            continue;
         }
+
+        let dest = extract_to.join(&relative);
+        entry.unpack(&dest)?;
     }
+
+    println!("Archive extracted successfully");
+    Ok(())
 }
 
 fn get_install_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let home = dirs::home_dir().ok_or("Unable to find home directory")?;
-    let install_dir = home.join("claude-proxy");
-    fs::create_dir_all(&install_dir)?;
-    Ok(install_dir)
+    crate::installer::get_install_directory()
 }
 
 fn add_to_system_path(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     println!("Adding to PATH: {:?}", path);
 
-    #[cfg(target_os = "windows")]
-    {
-        // CRITICAL: Use the safe add_to_path function from installer.rs to avoid truncation
-        // Never use setx directly on PATH as it truncates at 1024 characters
-        crate::installer::add_to_path(path)?;
-    }
+    // CRITICAL: Route through the shared add_to_path so Windows never risks
+    // setx's 1024-char truncation and Unix picks the user's actual shell
+    // profile instead of always assuming bash.
+    crate::installer::add_to_path(path)
+}
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Add to shell profile
-        let home = dirs::home_dir().ok_or("Unable to find home directory")?;
-        let profile_path = home.join(".bashrc");
+/// A Unix package manager capable of installing one of our dependencies.
+/// Homebrew is probed by path rather than `which` so Intel and Apple
+/// Silicon installs (`/usr/local` vs `/opt/homebrew`) are told apart even
+/// when both happen to be on PATH.
+#[cfg(not(target_os = "windows"))]
+struct UnixBackend {
+    label: &'static str,
+    program: PathBuf,
+    args: Vec<String>,
+    needs_sudo: bool,
+}
 
-        let export_line = format!("export PATH=\"$PATH:{}\"\n", path.display());
+/// Resolves the package managers usable on this machine for a dependency,
+/// in the order they should be tried. `brew_formula` names the Homebrew
+/// formula; `apt_pkgs`/`dnf_pkg`/`pacman_pkg` name the equivalents on Linux.
+#[cfg(not(target_os = "windows"))]
+fn unix_package_manager_backends(brew_formula: &str, apt_pkgs: &[&str], dnf_pkg: &str, pacman_pkg: &str) -> Vec<UnixBackend> {
+    let mut backends = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        for (label, path) in [
+            ("Homebrew (Apple Silicon)", "/opt/homebrew/bin/brew"),
+            ("Homebrew (Intel)", "/usr/local/bin/brew"),
+        ] {
+            let program = PathBuf::from(path);
+            if program.exists() {
+                backends.push(UnixBackend {
+                    label,
+                    program,
+                    args: vec!["install".to_string(), brew_formula.to_string()],
+                    needs_sudo: false,
+                });
+            }
+        }
+    }
 
-        std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(profile_path)?
-            .write_all(export_line.as_bytes())?;
+    if cfg!(target_os = "linux") {
+        if which::which("apt").is_ok() {
+            let mut args = vec!["install".to_string(), "-y".to_string()];
+            args.extend(apt_pkgs.iter().map(|s| s.to_string()));
+            backends.push(UnixBackend { label: "apt", program: PathBuf::from("apt"), args, needs_sudo: true });
+        } else if which::which("dnf").is_ok() {
+            backends.push(UnixBackend {
+                label: "dnf",
+                program: PathBuf::from("dnf"),
+                args: vec!["install".to_string(), "-y".to_string(), dnf_pkg.to_string()],
+                needs_sudo: true,
+            });
+        } else if which::which("pacman").is_ok() {
+            backends.push(UnixBackend {
+                label: "pacman",
+                program: PathBuf::from("pacman"),
+                args: vec!["-S".to_string(), "--noconfirm".to_string(), pacman_pkg.to_string()],
+                needs_sudo: true,
+            });
+        }
     }
 
-    Ok(())
+    backends
 }
 
 #[cfg(not(target_os = "windows"))]
-async fn install_python_unix() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    // Try different package managers
-    let commands = [
-        ("apt", vec!["sudo", "apt", "update", "&&", "sudo", "apt", "install", "-y", "python3", "python3-pip"]),
-        ("yum", vec!["sudo", "yum", "install", "-y", "python3", "python3-pip"]),
-        ("brew", vec!["brew", "install", "python3"]),
-    ];
-
-    for (manager, cmd) in &commands {
-        if which::which(manager).is_ok() {
-            let status = std::process::Command::new("sh")
-                .arg("-c")
-                .arg(&cmd.join(" "))
-                .status()?;
-
-            if status.success() {
-                println!("Python installed via {}", manager);
-                return Ok(PathBuf::from("/usr/bin/python3"));
-            }
-        }
+fn run_unix_backend(backend: &UnixBackend) -> std::io::Result<std::process::ExitStatus> {
+    if backend.needs_sudo {
+        std::process::Command::new("sudo").arg(&backend.program).args(&backend.args).status()
+    } else {
+        std::process::Command::new(&backend.program).args(&backend.args).status()
     }
+}
+
+/// The python-build-standalone target-triple suffix for this platform, so
+/// `install_python_unix` can pick the matching release asset. `None` means
+/// there's no managed build for this OS/arch combination.
+#[cfg(not(target_os = "windows"))]
+fn python_build_standalone_triple() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+        ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+        ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") => Some("aarch64-unknown-linux-gnu"),
+        _ => None,
+    }
+}
 
-    Err("Failed to install Python via package manager".into())
+/// Installs a self-contained python-build-standalone build rather than
+/// relying on whatever Python the system package manager happens to ship -
+/// that varies wildly in version and build options across distros, and some
+/// (like minimal container base images) don't have a package manager at all.
+#[cfg(not(target_os = "windows"))]
+async fn install_python_unix(requested_version: Option<&str>) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let triple = python_build_standalone_triple()
+        .ok_or("No managed Python build available for this platform (unsupported OS/architecture)")?;
+    let asset_suffix = format!("{}-install_only.tar.gz", triple);
+
+    let url = fetch_latest_asset_url(
+        "https://api.github.com/repos/indygreg/python-build-standalone/releases/latest",
+        &asset_suffix,
+        requested_version,
+    ).await?;
+
+    let install_dir = get_install_directory()?;
+    let python_dir = install_dir.join("python");
+    let download_path = install_dir.join("python-build-standalone.tar.gz");
+
+    println!("Downloading managed Python build: {}", url);
+    download_file(&url, &download_path).await?;
+    extract_archive(&download_path, &python_dir)?;
+
+    let python_bin_dir = python_dir.join("bin");
+    crate::installer::add_to_path(&python_bin_dir)?;
+
+    let python_bin = python_bin_dir.join("python3");
+    println!("Python installed to: {:?}", python_bin);
+    Ok(python_bin)
 }
 
 #[cfg(not(target_os = "windows"))]
 async fn install_nodejs_unix() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let commands = [
-        ("apt", "sudo apt update && sudo apt install -y nodejs npm"),
-        ("yum", "sudo yum install -y nodejs npm"),
-        ("brew", "brew install node"),
-    ];
-
-    for (manager, cmd) in &commands {
-        if which::which(manager).is_ok() {
-            let status = std::process::Command::new("sh")
-                .arg("-c")
-                .arg(cmd)
-                .status()?;
-
-            if status.success() {
-                println!("Node.js installed via {}", manager);
-                return Ok(PathBuf::from("/usr/bin/node"));
-            }
+    let backends = unix_package_manager_backends("node", &["nodejs", "npm"], "nodejs", "nodejs");
+    if backends.is_empty() {
+        return Err("No supported package manager found (Homebrew, apt, dnf, pacman). Install Node.js 16+ manually: https://nodejs.org/".into());
+    }
+
+    for backend in &backends {
+        println!("Trying {} to install Node.js...", backend.label);
+        if run_unix_backend(backend)?.success() {
+            println!("Node.js installed via {}", backend.label);
+            return Ok(PathBuf::from("/usr/bin/node"));
         }
     }
 
@@ -279,25 +805,103 @@ async fn install_nodejs_unix() -> Result<PathBuf, Box<dyn std::error::Error>> {
 
 #[cfg(not(target_os = "windows"))]
 async fn install_git_unix() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let commands = [
-        ("apt", "sudo apt install -y git"),
-        ("yum", "sudo yum install -y git"),
-        ("brew", "brew install git"),
-    ];
-
-    for (manager, cmd) in &commands {
-        if which::which(manager).is_ok() {
-            let status = std::process::Command::new("sh")
-                .arg("-c")
-                .arg(cmd)
-                .status()?;
-
-            if status.success() {
-                println!("Git installed via {}", manager);
-                return Ok(PathBuf::from("/usr/bin/git"));
-            }
+    let backends = unix_package_manager_backends("git", &["git"], "git", "git");
+    if backends.is_empty() {
+        return Err("No supported package manager found (Homebrew, apt, dnf, pacman). Install Git manually: https://git-scm.com/downloads".into());
+    }
+
+    for backend in &backends {
+        println!("Trying {} to install Git...", backend.label);
+        if run_unix_backend(backend)?.success() {
+            println!("Git installed via {}", backend.label);
+            return Ok(PathBuf::from("/usr/bin/git"));
         }
     }
 
     Err("Failed to install Git via package manager".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_sha256_for_name_matches_shasums_layout() {
+        let text = "09f257e25c649d1af6c8b6d22c8c6bdbec3bd99d9f9c3dbdaf8e5e51f5f5e5a5  node-v18.18.2-win-x64.zip\n\
+                     abc123  some-other-file.zip\n";
+        assert_eq!(
+            find_sha256_for_name(text, "node-v18.18.2-win-x64.zip"),
+            Some("09f257e25c649d1af6c8b6d22c8c6bdbec3bd99d9f9c3dbdaf8e5e51f5f5e5a5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_sha256_for_name_handles_release_notes_table() {
+        let text = "| `PortableGit-2.42.0.2-64-bit.7z.exe` | 09F257E25C649D1AF6C8B6D22C8C6BDBEC3BD99D9F9C3DBDAF8E5E51F5F5E5A5 |\n";
+        assert_eq!(
+            find_sha256_for_name(text, "PortableGit-2.42.0.2-64-bit.7z.exe"),
+            Some("09f257e25c649d1af6c8b6d22c8c6bdbec3bd99d9f9c3dbdaf8e5e51f5f5e5a5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_sha256_for_name_ignores_non_matching_lines() {
+        let text = "not a hash at all  other-file.zip\n";
+        assert_eq!(find_sha256_for_name(text, "node-v18.18.2-win-x64.zip"), None);
+    }
+
+    #[test]
+    fn test_first_hex64_token_reads_companion_sha256_file() {
+        let text = "09f257e25c649d1af6c8b6d22c8c6bdbec3bd99d9f9c3dbdaf8e5e51f5f5e5a5  cpython-3.11.6.tar.gz\n";
+        assert_eq!(
+            first_hex64_token(text),
+            Some("09f257e25c649d1af6c8b6d22c8c6bdbec3bd99d9f9c3dbdaf8e5e51f5f5e5a5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_asset_version_skips_unversioned_build_date() {
+        assert_eq!(
+            asset_version("cpython-3.11.6+20231002-x86_64-pc-windows-msvc-shared-install_only.tar.gz"),
+            Some((3, 11, 6))
+        );
+        assert_eq!(asset_version("PortableGit-2.42.0.2-64-bit.7z.exe"), Some((2, 42, 0)));
+        assert_eq!(asset_version("node-v18.18.2-win-x64.zip"), None);
+    }
+
+    #[test]
+    fn test_version_matches_narrows_by_requested_prefix() {
+        assert!(version_matches((3, 11, 6), None));
+        assert!(version_matches((3, 11, 6), Some("3.11")));
+        assert!(version_matches((3, 11, 6), Some("3.11.6")));
+        assert!(!version_matches((3, 12, 0), Some("3.11")));
+        assert!(!version_matches((3, 11, 5), Some("3.11.6")));
+    }
+
+    #[test]
+    fn test_pick_release_asset_picks_newest_matching_suffix() {
+        let release = GithubRelease {
+            body: None,
+            assets: vec![
+                GithubAsset {
+                    name: "cpython-3.9.18+20231002-x86_64-pc-windows-msvc-shared-install_only.tar.gz".to_string(),
+                    browser_download_url: "https://example.com/3.9".to_string(),
+                },
+                GithubAsset {
+                    name: "cpython-3.12.0+20231002-x86_64-pc-windows-msvc-shared-install_only.tar.gz".to_string(),
+                    browser_download_url: "https://example.com/3.12".to_string(),
+                },
+                GithubAsset {
+                    name: "cpython-3.11.6+20231002-x86_64-pc-windows-msvc-shared-install_only.tar.gz".to_string(),
+                    browser_download_url: "https://example.com/3.11".to_string(),
+                },
+            ],
+        };
+
+        let picked = pick_release_asset(&release, "x86_64-pc-windows-msvc-shared-install_only.tar.gz", None).unwrap();
+        assert_eq!(picked.browser_download_url, "https://example.com/3.12");
+
+        let picked = pick_release_asset(&release, "x86_64-pc-windows-msvc-shared-install_only.tar.gz", Some("3.11")).unwrap();
+        assert_eq!(picked.browser_download_url, "https://example.com/3.11");
+    }
 }
\ No newline at end of file
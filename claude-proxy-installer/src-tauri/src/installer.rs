@@ -5,6 +5,30 @@ use std::process::Command;
 pub async fn install_claude_cli() -> Result<(), Box<dyn std::error::Error>> {
     println!("Installing Claude Code...");
 
+    // Try the system package manager(s) first - these integrate with the
+    // user's own PATH handling instead of relying on a global npm prefix.
+    for backend in package_manager_backends() {
+        println!("Trying package manager backend: {}", backend.label);
+
+        let status = Command::new(&backend.program).args(&backend.args).status();
+        match status {
+            Ok(status) if status.success() => {
+                if verify_claude_cli().await? {
+                    println!("Claude Code installed successfully via {}", backend.label);
+                    return Ok(());
+                }
+                println!("{} reported success but verification failed", backend.label);
+            }
+            Ok(status) => println!("{} failed with status: {}", backend.label, status),
+            Err(e) => println!("{} failed to run: {}", backend.label, e),
+        }
+    }
+
+    if matches!(crate::dependency_detector::detect_nodejs().await, crate::dependency_detector::DependencyStatus::NotFound) {
+        println!("No Node.js/npm found - bootstrapping a standalone Node.js install first");
+        crate::downloader::bootstrap_nodejs().await?;
+    }
+
     // First try to find npm in common installation paths
     let npm_paths = get_npm_paths();
 
@@ -58,6 +82,64 @@ pub async fn install_claude_cli() -> Result<(), Box<dyn std::error::Error>> {
     install_claude_cli_local().await
 }
 
+/// A system package manager capable of installing Claude Code, along with
+/// the concrete command used to invoke it. `label` is surfaced in the final
+/// success message so users can see which tool actually did the install.
+struct PackageManagerBackend {
+    label: &'static str,
+    program: PathBuf,
+    args: Vec<&'static str>,
+}
+
+/// Detects which package managers are usable on this machine, in the order
+/// they should be tried. On macOS both Homebrew variants are probed
+/// independently (Apple Silicon installs to `/opt/homebrew`, Intel to
+/// `/usr/local`) so a Rosetta-installed brew doesn't shadow a native one.
+fn package_manager_backends() -> Vec<PackageManagerBackend> {
+    let mut backends = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        for (label, path) in [
+            ("Homebrew (Apple Silicon)", "/opt/homebrew/bin/brew"),
+            ("Homebrew (Intel)", "/usr/local/bin/brew"),
+        ] {
+            let program = PathBuf::from(path);
+            if program.exists() {
+                backends.push(PackageManagerBackend { label, program, args: vec!["install", "claude-code"] });
+            }
+        }
+    }
+
+    if cfg!(target_os = "linux") {
+        if which::which("apt").is_ok() {
+            backends.push(PackageManagerBackend {
+                label: "apt",
+                program: PathBuf::from("apt"),
+                args: vec!["install", "-y", "claude-code"],
+            });
+        }
+    }
+
+    if cfg!(target_os = "windows") {
+        if which::which("winget").is_ok() {
+            backends.push(PackageManagerBackend {
+                label: "winget",
+                program: PathBuf::from("winget"),
+                args: vec!["install", "--silent", "--id", "Anthropic.ClaudeCode"],
+            });
+        }
+        if which::which("choco").is_ok() {
+            backends.push(PackageManagerBackend {
+                label: "Chocolatey",
+                program: PathBuf::from("choco"),
+                args: vec!["install", "-y", "claude-code"],
+            });
+        }
+    }
+
+    backends
+}
+
 fn get_npm_paths() -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
@@ -161,26 +243,32 @@ async fn install_claude_cli_local() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-pub async fn install_proxy_scripts() -> Result<(), Box<dyn std::error::Error>> {
+/// Every embedded proxy script across every provider, shared by the doctor
+/// report so it can fingerprint what's available regardless of which
+/// providers a given install actually selected.
+pub fn embedded_proxy_scripts() -> Vec<(&'static str, &'static str)> {
+    let mut scripts = crate::providers::shared_scripts();
+    for provider in crate::providers::all_providers() {
+        scripts.extend(provider.scripts);
+    }
+    scripts
+}
+
+/// Installs the shared scripts plus every selected provider's scripts.
+/// `provider_selection` is the same string `--provider` accepts (`"all"`,
+/// or a comma-separated list of provider ids).
+pub async fn install_proxy_scripts(provider_selection: &str) -> Result<(), Box<dyn std::error::Error>> {
     println!("=== STARTING PROXY SCRIPTS INSTALLATION ===");
 
     let install_dir = get_install_directory()?;
     println!("Install directory: {:?}", install_dir);
 
-    // Embed actual proxy files from main directory using include_str!
-    let scripts = vec![
-        ("xai_claude_proxy_enhanced.py", include_str!("../../../xai_claude_proxy_enhanced.py")),
-        ("groq_claude_proxy_enhanced.py", include_str!("../../../groq_claude_proxy_enhanced.py")),
-        ("proxy_core.py", include_str!("../../../proxy_core.py")),
-        ("proxy_common.py", include_str!("../../../proxy_common.py")),
-        ("xai_adapter.py", include_str!("../../../xai_adapter.py")),
-        ("groq_adapter.py", include_str!("../../../groq_adapter.py")),
-        ("claudeproxy.bat", include_str!("../../../claudeproxy.bat")),
-        ("claudeproxy.sh", include_str!("../../../claudeproxy.sh")),
-        ("start_xai_proxy.bat", include_str!("../../../start_xai_proxy.bat")),
-        ("start_groq_proxy.bat", include_str!("../../../start_groq_proxy.bat")),
-        ("claudeproxysetup.py", include_str!("../../../claudeproxysetup.py")),
-    ];
+    let providers = crate::providers::resolve_selection(provider_selection);
+    let mut scripts = crate::providers::shared_scripts();
+    for provider in &providers {
+        println!("Including provider: {}", provider.display_name);
+        scripts.extend(provider.scripts.clone());
+    }
 
     println!("Installing {} proxy scripts from embedded sources...", scripts.len());
 
@@ -227,13 +315,16 @@ pub async fn install_proxy_scripts() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-pub async fn create_shortcuts() -> Result<(), Box<dyn std::error::Error>> {
+pub async fn create_shortcuts(provider_selection: &str) -> Result<(), Box<dyn std::error::Error>> {
     println!("=== STARTING SHORTCUTS CREATION ===");
 
     let install_dir = get_install_directory()?;
     println!("Install directory for shortcuts: {:?}", install_dir);
 
-    // Simplified shortcut creation - just create basic batch files
+    let providers = crate::providers::resolve_selection(provider_selection);
+
+    // Simplified shortcut creation - just create basic batch files, one per
+    // selected provider, derived from that provider's launcher template.
     #[cfg(target_os = "windows")]
     {
         let desktop = match dirs::desktop_dir() {
@@ -244,17 +335,12 @@ pub async fn create_shortcuts() -> Result<(), Box<dyn std::error::Error>> {
             }
         };
 
-        let install_dir = get_install_directory()?;
-        let shortcuts = vec![
-            ("xAI Claude Proxy.bat", format!("@echo off\ncd /d \"{}\"\ncall start_xai_proxy.bat", install_dir.display())),
-            ("GroqCloud Claude Proxy.bat", format!("@echo off\ncd /d \"{}\"\ncall start_groq_proxy.bat", install_dir.display())),
-        ];
-
-        for (filename, content) in shortcuts {
-            let shortcut_path = desktop.join(filename);
+        for provider in &providers {
+            let content = format!("@echo off\ncd /d \"{}\"\ncall {}", install_dir.display(), provider.launcher_name);
+            let shortcut_path = desktop.join(provider.shortcut_name);
             match fs::write(&shortcut_path, &content) {
                 Ok(_) => println!("✓ Created shortcut: {:?}", shortcut_path),
-                Err(e) => println!("⚠ Failed to create shortcut {}: {}", filename, e),
+                Err(e) => println!("⚠ Failed to create shortcut {}: {}", provider.shortcut_name, e),
             }
         }
     }
@@ -262,6 +348,7 @@ pub async fn create_shortcuts() -> Result<(), Box<dyn std::error::Error>> {
     // Skip shortcuts on Unix for now to avoid hanging
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = &providers;
         println!("⚠ Skipping shortcuts creation on Unix to avoid hanging");
     }
 
@@ -269,9 +356,15 @@ pub async fn create_shortcuts() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn get_install_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let home = dirs::home_dir().ok_or("Unable to find home directory")?;
-    let install_dir = home.join("claude-proxy");
+/// Resolves the app's data directory via `directories`, which knows the
+/// right per-platform convention (`~/.local/share/claude-proxy` on Linux,
+/// `~/Library/Application Support/com.ClaudeProxy.claude-proxy` on macOS,
+/// `%APPDATA%\ClaudeProxy\claude-proxy\data` on Windows) instead of always
+/// assuming a `claude-proxy` folder directly under the home directory.
+pub(crate) fn get_install_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let project_dirs = directories::ProjectDirs::from("com", "ClaudeProxy", "claude-proxy")
+        .ok_or("Unable to determine install directory for this platform")?;
+    let install_dir = project_dirs.data_dir().to_path_buf();
     fs::create_dir_all(&install_dir)?;
     Ok(install_dir)
 }
@@ -298,7 +391,7 @@ fn make_scripts_executable(dir: &Path) -> Result<(), Box<dyn std::error::Error>>
 
 
 
-fn is_in_path(path: &Path) -> Result<bool, Box<dyn std::error::Error>> {
+pub(crate) fn is_in_path(path: &Path) -> Result<bool, Box<dyn std::error::Error>> {
     let path_str = path.to_str().ok_or("Invalid path")?;
 
     #[cfg(target_os = "windows")]
@@ -402,18 +495,81 @@ pub fn add_to_path(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
 
     #[cfg(not(target_os = "windows"))]
     {
-        let home = dirs::home_dir().ok_or("Unable to find home directory")?;
-        let profile_path = home.join(".bashrc");
+        let (profile_path, shell_kind) = detect_shell_profile();
+        let path_str = path.to_string_lossy().into_owned();
 
-        let export_line = format!("export PATH=\"$PATH:{}\"\n", path.display());
+        let already_present = fs::read_to_string(&profile_path)
+            .map(|contents| contents.contains(&path_str))
+            .unwrap_or(false);
+
+        if already_present {
+            println!("{} already references {:?}, skipping (idempotent)", profile_path.display(), path);
+        } else {
+            let export_line = shell_kind.path_export_line(path);
+
+            if let Some(parent) = profile_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            use std::io::Write;
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&profile_path)?
+                .write_all(export_line.as_bytes())?;
+
+            println!("Added {:?} to {}", path, profile_path.display());
+        }
 
-        use std::io::Write;
-        std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(profile_path)?
-            .write_all(export_line.as_bytes())?;
+        // GUI-launched macOS apps don't source shell profiles at all, so
+        // also propagate PATH to the current login session via launchctl.
+        #[cfg(target_os = "macos")]
+        {
+            let current_path = std::env::var("PATH").unwrap_or_default();
+            let _ = Command::new("launchctl")
+                .args(["setenv", "PATH", &format!("{}:{}", current_path, path_str)])
+                .status();
+        }
     }
 
     Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl ShellKind {
+    fn path_export_line(&self, path: &Path) -> String {
+        match self {
+            ShellKind::Fish => format!("fish_add_path {}\n", path.display()),
+            ShellKind::Bash | ShellKind::Zsh => format!("export PATH=\"$PATH:{}\"\n", path.display()),
+        }
+    }
+}
+
+/// Picks the rc file that will actually get sourced for this user, based on
+/// `$SHELL` (and `$ZDOTDIR` for zsh users who relocate their dotfiles).
+/// macOS defaults to zsh since Catalina, so a blind `.bashrc` append is a
+/// no-op there; fish keeps its config and PATH syntax entirely separate.
+fn detect_shell_profile() -> (PathBuf, ShellKind) {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    let shell = std::env::var("SHELL").unwrap_or_default();
+
+    if shell.contains("fish") {
+        let config_dir = home.join(".config").join("fish");
+        (config_dir.join("config.fish"), ShellKind::Fish)
+    } else if shell.contains("zsh") {
+        let zdotdir = std::env::var("ZDOTDIR").map(PathBuf::from).unwrap_or_else(|_| home.clone());
+        (zdotdir.join(".zshrc"), ShellKind::Zsh)
+    } else if cfg!(target_os = "macos") {
+        // Terminal.app on macOS starts a login shell, which reads
+        // .bash_profile rather than .bashrc.
+        (home.join(".bash_profile"), ShellKind::Bash)
+    } else {
+        (home.join(".bashrc"), ShellKind::Bash)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::Serialize;
+use tauri::{Emitter, Window};
+
+use crate::installer;
+
+/// Mirrors a `log::Record` in a shape the frontend can render - one line
+/// per entry, same as what lands in the log file.
+#[derive(Debug, Clone, Serialize)]
+struct LogMessage {
+    level: String,
+    target: String,
+    message: String,
+}
+
+/// Writes every record at `Info` level or above (lower levels are dropped)
+/// to `<install dir>/installer.log` and, once a window
+/// has been attached via `init`, forwards it to the webview as `log-message`
+/// so install progress can be watched live instead of only after the fact.
+struct InstallerLogger {
+    file: Mutex<std::fs::File>,
+    window: Mutex<Option<Window>>,
+}
+
+impl Log for InstallerLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!("[{}] {}: {}\n", record.level(), record.target(), record.args());
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+
+        if let Ok(window) = self.window.lock() {
+            if let Some(window) = window.as_ref() {
+                let message = LogMessage {
+                    level: record.level().to_string(),
+                    target: record.target().to_string(),
+                    message: record.args().to_string(),
+                };
+                let _ = window.emit("log-message", &message);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+static LOGGER: OnceLock<InstallerLogger> = OnceLock::new();
+
+/// Resolves the path the logger writes to, without requiring it to be
+/// initialized first - used by `get_log_path` so the UI can offer an
+/// "open log file" link even before any installation step has run.
+pub fn log_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(installer::get_install_directory()?.join("installer.log"))
+}
+
+/// Installs the global logger on first call and (every call) attaches the
+/// given window so subsequent `log::info!`/`log::warn!`/`log::error!` calls
+/// stream to it. Safe to call once per Tauri command invocation.
+pub fn init(window: Window) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let log_path = log_file_path()?;
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let logger = LOGGER.get_or_init(|| {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .expect("Failed to open installer log file");
+        InstallerLogger { file: Mutex::new(file), window: Mutex::new(None) }
+    });
+
+    if let Ok(mut current) = logger.window.lock() {
+        *current = Some(window);
+    }
+
+    // `set_logger` errors if already set by an earlier command - that's
+    // fine, the window swap above is the part that needs to happen again.
+    let _ = log::set_logger(logger).map(|_| log::set_max_level(LevelFilter::Info));
+
+    Ok(log_path)
+}
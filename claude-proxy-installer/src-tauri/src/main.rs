@@ -1,18 +1,27 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tauri::{Emitter, Window};
 use serde::{Deserialize, Serialize};
 
+mod cmd;
 mod dependency_detector;
+mod doctor;
 mod downloader;
 mod installer;
+mod logging;
+mod providers;
+mod updater;
+
+use cmd::Cmd;
 
 use dependency_detector::{DependencyStatus, *};
+use doctor::DoctorReport;
 use downloader::*;
 use installer::*;
+use providers::ProviderInfo;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ProgressUpdate {
@@ -31,8 +40,10 @@ struct ConfigurationResult {
 
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
-async fn detect_dependencies(window: Window) -> Result<DetectionResults, String> {
-    println!("Detecting system dependencies...");
+async fn detect_dependencies(window: Window, install_dir: Option<String>) -> Result<DetectionResults, String> {
+    let _ = logging::init(window.clone());
+    log::info!("Detecting system dependencies...");
+    let install_dir_override = install_dir.as_ref().map(PathBuf::from);
 
     let emit_progress = |step: &str, status: &str, details: &str, progress: u8| {
         let update = ProgressUpdate {
@@ -52,6 +63,7 @@ async fn detect_dependencies(window: Window) -> Result<DetectionResults, String>
     let python_status = detect_python().await;
     match &python_status {
         DependencyStatus::Found(version) => emit_progress("python", "completed", &format!("✓ Found Python {}", version), 12),
+        DependencyStatus::Outdated { found, required } => emit_progress("python", "pending", &format!("✗ Python {} too old, need ≥{} - will upgrade", found, required), 12),
         DependencyStatus::NotFound => emit_progress("python", "pending", "✗ Python not found - will install", 12),
     }
 
@@ -59,6 +71,7 @@ async fn detect_dependencies(window: Window) -> Result<DetectionResults, String>
     let nodejs_status = detect_nodejs().await;
     match &nodejs_status {
         DependencyStatus::Found(version) => emit_progress("nodejs", "completed", &format!("✓ Found Node.js {}", version), 14),
+        DependencyStatus::Outdated { found, required } => emit_progress("nodejs", "pending", &format!("✗ Node.js {} too old, need ≥{} - will upgrade", found, required), 14),
         DependencyStatus::NotFound => emit_progress("nodejs", "pending", "✗ Node.js not found - will install", 14),
     }
 
@@ -66,13 +79,15 @@ async fn detect_dependencies(window: Window) -> Result<DetectionResults, String>
     let git_status = detect_git().await;
     match &git_status {
         DependencyStatus::Found(version) => emit_progress("git", "completed", &format!("✓ Found Git {}", version), 16),
+        DependencyStatus::Outdated { found, required } => emit_progress("git", "pending", &format!("✗ Git {} too old, need ≥{} - will upgrade", found, required), 16),
         DependencyStatus::NotFound => emit_progress("git", "pending", "✗ Git not found - will install", 16),
     }
 
     emit_progress("claude", "active", "Checking for Claude Code...", 17);
-    let claude_status = detect_claude().await;
+    let claude_status = detect_claude(install_dir_override.as_deref()).await;
     match &claude_status {
         DependencyStatus::Found(version) => emit_progress("claude", "completed", &format!("✓ Found {}", version), 18),
+        DependencyStatus::Outdated { found, required } => emit_progress("claude", "pending", &format!("✗ Claude Code {} too old, need ≥{} - will upgrade", found, required), 18),
         DependencyStatus::NotFound => emit_progress("claude", "pending", "✗ Claude Code not found - will install", 18),
     }
 
@@ -81,6 +96,7 @@ async fn detect_dependencies(window: Window) -> Result<DetectionResults, String>
         nodejs: nodejs_status,
         git: git_status,
         claude: claude_status,
+        install_dir_override: install_dir,
     };
 
     emit_progress("detect", "completed", "System scan complete", 19);
@@ -88,11 +104,146 @@ async fn detect_dependencies(window: Window) -> Result<DetectionResults, String>
     Ok(detection_results)
 }
 
+/// "Check for updates": unlike `start_installation`, this only acts on
+/// tools that are already `Found`/`Outdated` - anything `NotFound` is left
+/// alone since that's what first-time install is for.
+#[tauri::command]
+async fn update_dependencies(window: Window, detection_results: DetectionResults) -> Result<(), String> {
+    let emit_progress = |step: &str, status: &str, details: &str, progress: u8| {
+        let update = ProgressUpdate { step: step.to_string(), status: status.to_string(), details: details.to_string(), progress };
+        let _ = window.emit("installation-progress", &update);
+    };
+    let emit_error = |message: &str| {
+        let error = serde_json::json!({ "message": message });
+        let _ = window.emit("installation-error", &error);
+    };
+
+    // Step 1: Python
+    if updater::is_present(&detection_results.python) {
+        emit_progress("python", "active", "Updating Python...", 25);
+        if let Err(e) = updater::update_python().await {
+            emit_error(&format!("Failed to update Python: {}", e));
+            return Err(format!("Python update failed: {}", e));
+        }
+        emit_progress("python", "completed", "Python updated", 25);
+    } else {
+        emit_progress("python", "completed", "Python not installed - nothing to update", 25);
+    }
+
+    // Step 2: Node.js
+    if updater::is_present(&detection_results.nodejs) {
+        emit_progress("nodejs", "active", "Updating Node.js...", 50);
+        if let Err(e) = updater::update_nodejs().await {
+            emit_error(&format!("Failed to update Node.js: {}", e));
+            return Err(format!("Node.js update failed: {}", e));
+        }
+        emit_progress("nodejs", "completed", "Node.js updated", 50);
+    } else {
+        emit_progress("nodejs", "completed", "Node.js not installed - nothing to update", 50);
+    }
+
+    // Step 3: Git
+    if updater::is_present(&detection_results.git) {
+        emit_progress("git", "active", "Updating Git...", 75);
+        if let Err(e) = updater::update_git().await {
+            emit_error(&format!("Failed to update Git: {}", e));
+            return Err(format!("Git update failed: {}", e));
+        }
+        emit_progress("git", "completed", "Git updated", 75);
+    } else {
+        emit_progress("git", "completed", "Git not installed - nothing to update", 75);
+    }
+
+    // Step 4: Claude Code
+    if updater::is_present(&detection_results.claude) {
+        emit_progress("claude", "active", "Updating Claude Code...", 100);
+        if let Err(e) = updater::update_claude_cli().await {
+            emit_error(&format!("Failed to update Claude Code: {}", e));
+            return Err(format!("Claude Code update failed: {}", e));
+        }
+        emit_progress("claude", "completed", "Claude Code updated", 100);
+    } else {
+        emit_progress("claude", "completed", "Claude Code not installed - nothing to update", 100);
+    }
+
+    Ok(())
+}
+
+/// Lists installable providers (`--provider xai,groq` or `all`) so the UI
+/// can offer a subset selection instead of always installing everything.
+#[tauri::command]
+fn list_providers() -> Vec<ProviderInfo> {
+    providers::list_provider_info()
+}
+
+/// Which parts of `start_installation` actually run. Lets someone who only
+/// wants the proxy scripts (or who already has Python/Node/Git/Claude Code)
+/// skip the dependency-install steps instead of always doing everything.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InstallProfile {
+    Full,
+    Minimal,
+    ProxyOnly,
+}
+
+impl InstallProfile {
+    fn parse(raw: &str) -> Self {
+        match raw.to_lowercase().replace(['-', '_'], "").as_str() {
+            "minimal" => InstallProfile::Minimal,
+            "proxyonly" => InstallProfile::ProxyOnly,
+            _ => InstallProfile::Full,
+        }
+    }
+
+    /// Whether Python/Node.js/Git/Claude Code get installed at all.
+    fn installs_dependencies(self) -> bool {
+        !matches!(self, InstallProfile::ProxyOnly)
+    }
+
+    /// Whether desktop shortcuts get created after the proxy scripts land.
+    fn creates_shortcuts(self) -> bool {
+        !matches!(self, InstallProfile::Minimal)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProfileInfo {
+    id: String,
+    #[serde(rename = "displayName")]
+    display_name: String,
+    description: String,
+}
+
+/// Lists the install profiles the UI can offer alongside provider selection.
+#[tauri::command]
+fn list_profiles() -> Vec<ProfileInfo> {
+    vec![
+        ProfileInfo {
+            id: "full".to_string(),
+            display_name: "Full install".to_string(),
+            description: "Installs Python, Node.js, Git, Claude Code, proxy scripts, and desktop shortcuts.".to_string(),
+        },
+        ProfileInfo {
+            id: "minimal".to_string(),
+            display_name: "Minimal".to_string(),
+            description: "Installs dependencies and proxy scripts, but skips desktop shortcuts.".to_string(),
+        },
+        ProfileInfo {
+            id: "proxyOnly".to_string(),
+            display_name: "Proxy scripts only".to_string(),
+            description: "Assumes Python, Node.js, Git, and Claude Code are already installed; only sets up proxy scripts and shortcuts.".to_string(),
+        },
+    ]
+}
+
 #[tauri::command]
-async fn start_installation(window: Window, detection_results: DetectionResults) -> Result<(), String> {
-    println!("=== START_INSTALLATION COMMAND INVOKED ===");
-    println!("Received detection results: {:?}", detection_results);
-    println!("===========================================");
+async fn start_installation(window: Window, detection_results: DetectionResults, provider: Option<String>, profile: Option<String>, force_download: Option<bool>) -> Result<(), String> {
+    let provider_selection = provider.unwrap_or_else(|| "all".to_string());
+    let install_profile = InstallProfile::parse(&profile.unwrap_or_else(|| "full".to_string()));
+    let force_download = force_download.unwrap_or(false);
+    let _ = logging::init(window.clone());
+    log::info!("Starting installation (profile: {:?}, provider: {})", install_profile, provider_selection);
+    log::info!("Detection results: {:?}", detection_results);
 
     let emit_progress = |step: &str, status: &str, details: &str, progress: u8| {
         let update = ProgressUpdate {
@@ -109,14 +260,26 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
         let _ = window.emit("installation-error", &error);
     };
 
-    // Step 2: Install Python if needed
+    // Steps 2-5: dependency installs are skipped entirely under the
+    // proxy-only profile, which assumes Python/Node.js/Git/Claude Code are
+    // already present.
+    if !install_profile.installs_dependencies() {
+        emit_progress("python", "completed", "Skipped - proxy-only profile", 25);
+        emit_progress("nodejs", "completed", "Skipped - proxy-only profile", 40);
+        emit_progress("git", "completed", "Skipped - proxy-only profile", 55);
+        emit_progress("claude", "completed", "Skipped - proxy-only profile", 75);
+    } else {
+
+    // Step 2: Install Python if needed (an Outdated install is routed
+    // through the same install branch as NotFound - it just gets its own
+    // progress message so the user understands why we're reinstalling).
     match &detection_results.python {
         DependencyStatus::Found(_) => {
             emit_progress("python", "completed", "✓ Python already installed - skipping", 25);
         }
-        DependencyStatus::NotFound => {
-            emit_progress("python", "active", "Installing Python...", 22);
-            match download_python().await {
+        DependencyStatus::Outdated { found, required } => {
+            emit_progress("python", "active", &format!("Python {} too old, upgrading to ≥{}...", found, required), 22);
+            match download_python(None).await {
                 Ok(_) => emit_progress("python", "completed", "Python installed successfully", 25),
                 Err(e) => {
                     emit_error(&format!("Failed to install Python: {}", e));
@@ -124,6 +287,21 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
                 }
             }
         }
+        DependencyStatus::NotFound => {
+            let existing = (!force_download).then(downloader::find_existing_python).flatten();
+            if let Some(existing) = existing {
+                emit_progress("python", "completed", &format!("✓ Found existing Python at {} - reusing it", existing.display()), 25);
+            } else {
+                emit_progress("python", "active", "Installing Python...", 22);
+                match download_python(None).await {
+                    Ok(_) => emit_progress("python", "completed", "Python installed successfully", 25),
+                    Err(e) => {
+                        emit_error(&format!("Failed to install Python: {}", e));
+                        return Err(format!("Python installation failed: {}", e));
+                    }
+                }
+            }
+        }
     }
 
     // Step 3: Install Node.js if needed
@@ -131,8 +309,8 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
         DependencyStatus::Found(_) => {
             emit_progress("nodejs", "completed", "✓ Node.js already installed - skipping", 40);
         }
-        DependencyStatus::NotFound => {
-            emit_progress("nodejs", "active", "Installing Node.js...", 35);
+        DependencyStatus::Outdated { found, required } => {
+            emit_progress("nodejs", "active", &format!("Node.js {} too old, upgrading to ≥{}...", found, required), 35);
             match download_nodejs().await {
                 Ok(_) => emit_progress("nodejs", "completed", "Node.js installed successfully", 40),
                 Err(e) => {
@@ -141,6 +319,21 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
                 }
             }
         }
+        DependencyStatus::NotFound => {
+            let existing = (!force_download).then(downloader::find_existing_nodejs).flatten();
+            if let Some(existing) = existing {
+                emit_progress("nodejs", "completed", &format!("✓ Found existing Node.js at {} - reusing it", existing.display()), 40);
+            } else {
+                emit_progress("nodejs", "active", "Installing Node.js...", 35);
+                match download_nodejs().await {
+                    Ok(_) => emit_progress("nodejs", "completed", "Node.js installed successfully", 40),
+                    Err(e) => {
+                        emit_error(&format!("Failed to install Node.js: {}", e));
+                        return Err(format!("Node.js installation failed: {}", e));
+                    }
+                }
+            }
+        }
     }
 
     // Step 4: Install Git if needed
@@ -148,9 +341,9 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
         DependencyStatus::Found(_) => {
             emit_progress("git", "completed", "✓ Git already installed - skipping", 55);
         }
-        DependencyStatus::NotFound => {
-            emit_progress("git", "active", "Installing Git...", 50);
-            match download_git().await {
+        DependencyStatus::Outdated { found, required } => {
+            emit_progress("git", "active", &format!("Git {} too old, upgrading to ≥{}...", found, required), 50);
+            match download_git(None).await {
                 Ok(_) => emit_progress("git", "completed", "Git installed successfully", 55),
                 Err(e) => {
                     emit_error(&format!("Failed to install Git: {}", e));
@@ -158,6 +351,21 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
                 }
             }
         }
+        DependencyStatus::NotFound => {
+            let existing = (!force_download).then(downloader::find_existing_git).flatten();
+            if let Some(existing) = existing {
+                emit_progress("git", "completed", &format!("✓ Found existing Git at {} - reusing it", existing.display()), 55);
+            } else {
+                emit_progress("git", "active", "Installing Git...", 50);
+                match download_git(None).await {
+                    Ok(_) => emit_progress("git", "completed", "Git installed successfully", 55),
+                    Err(e) => {
+                        emit_error(&format!("Failed to install Git: {}", e));
+                        return Err(format!("Git installation failed: {}", e));
+                    }
+                }
+            }
+        }
     }
 
     // Step 5: Install Claude Code if needed
@@ -165,6 +373,16 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
         DependencyStatus::Found(_) => {
             emit_progress("claude", "completed", "✓ Claude Code already installed - skipping", 75);
         }
+        DependencyStatus::Outdated { found, required } => {
+            emit_progress("claude", "active", &format!("Claude Code {} too old, upgrading to ≥{}...", found, required), 65);
+            match install_claude_cli().await {
+                Ok(_) => emit_progress("claude", "completed", "Claude Code installed successfully", 75),
+                Err(e) => {
+                    emit_error(&format!("Failed to install Claude Code: {}", e));
+                    return Err(format!("Claude Code installation failed: {}", e));
+                }
+            }
+        }
         DependencyStatus::NotFound => {
             emit_progress("claude", "active", "Installing Claude Code...", 65);
             match install_claude_cli().await {
@@ -177,13 +395,15 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
         }
     }
 
+    } // end dependency installs (skipped under proxy-only profile)
+
     // Step 6: Install proxy scripts
     emit_progress("proxy", "active", "Installing proxy scripts...", 80);
-    println!("EMITTING: proxy active");
+    log::info!("Installing proxy scripts");
 
-    match install_proxy_scripts().await {
+    match install_proxy_scripts(&provider_selection).await {
         Ok(_) => {
-            println!("EMITTING: proxy completed");
+            log::info!("Proxy scripts installed");
             emit_progress("proxy", "completed", "Proxy scripts installed successfully", 90);
         },
         Err(e) => {
@@ -192,22 +412,26 @@ async fn start_installation(window: Window, detection_results: DetectionResults)
         }
     }
 
-    // Step 7: Create shortcuts
-    emit_progress("shortcuts", "active", "Creating desktop shortcuts...", 95);
-    println!("EMITTING: shortcuts active");
-
-    match create_shortcuts().await {
-        Ok(_) => {
-            println!("EMITTING: shortcuts completed");
-            emit_progress("shortcuts", "completed", "Installation complete!", 100);
-        },
-        Err(e) => {
-            emit_error(&format!("Failed to create shortcuts: {}", e));
-            return Err(format!("Shortcut creation failed: {}", e));
+    // Step 7: Create shortcuts (skipped under the minimal profile)
+    if install_profile.creates_shortcuts() {
+        emit_progress("shortcuts", "active", "Creating desktop shortcuts...", 95);
+        log::info!("Creating desktop shortcuts");
+
+        match create_shortcuts(&provider_selection).await {
+            Ok(_) => {
+                log::info!("Shortcuts created");
+                emit_progress("shortcuts", "completed", "Installation complete!", 100);
+            },
+            Err(e) => {
+                emit_error(&format!("Failed to create shortcuts: {}", e));
+                return Err(format!("Shortcut creation failed: {}", e));
+            }
         }
+    } else {
+        emit_progress("shortcuts", "completed", "Skipped - minimal profile", 100);
     }
 
-    println!("Installation completed successfully!");
+    log::info!("Installation completed successfully");
     Ok(())
 }
 
@@ -328,6 +552,31 @@ async fn save_configuration(
     })
 }
 
+/// `doctor`/`info`: a full environment report for bug reports and CI checks.
+/// When `json` is false this also prints a colorized human table to stdout;
+/// the returned `DoctorReport` already derives `Serialize`, so the `--json`
+/// form is just the raw IPC response.
+#[tauri::command]
+async fn get_doctor_report(json: bool) -> Result<DoctorReport, String> {
+    let report = doctor::build_report().await.map_err(|e| format!("Failed to build doctor report: {}", e))?;
+
+    if !json {
+        println!("{}", doctor::render_table(&report));
+    }
+
+    Ok(report)
+}
+
+/// Path to the installer's log file, so the UI can offer to open it (or a
+/// support request can point a user at it) without needing an install to be
+/// in progress first.
+#[tauri::command]
+fn get_log_path() -> Result<String, String> {
+    logging::log_file_path()
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to resolve log path: {}", e))
+}
+
 #[tauri::command]
 async fn launch_proxy() -> Result<(), String> {
     println!("Opening command prompt with claudeproxy instructions...");
@@ -359,8 +608,8 @@ pause > nul
         std::fs::write(&temp_batch, instructions)
             .map_err(|e| format!("Failed to create instructions: {}", e))?;
 
-        Command::new("cmd")
-            .args(&["/c", "start", "cmd", "/k", temp_batch.to_str().unwrap()])
+        Cmd::new("true", "cmd /c start cmd /k")
+            .arg(temp_batch.to_string_lossy().into_owned())
             .spawn()
             .map_err(|e| format!("Failed to open command prompt: {}", e))?;
     }
@@ -377,67 +626,27 @@ pause > nul
 
 #[tauri::command]
 async fn open_url(url: String) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("cmd")
-            .args(&["/c", "start", &url])
-            .spawn()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg(&url)
-            .spawn()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
-    }
+    let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
 
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open")
-            .arg(&url)
-            .spawn()
-            .map_err(|e| format!("Failed to open URL: {}", e))?;
-    }
-
-    Ok(())
+    Cmd::new(opener, "cmd /c start")
+        .arg(url)
+        .spawn()
+        .map_err(|e| format!("Failed to open URL: {}", e))
 }
 
 #[tauri::command]
 async fn open_install_folder() -> Result<(), String> {
     let install_path = get_install_path().map_err(|e| format!("Failed to get install path: {}", e))?;
+    let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
 
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("explorer")
-            .arg(install_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .arg(install_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
-    }
-
-    #[cfg(target_os = "linux")]
-    {
-        Command::new("xdg-open")
-            .arg(install_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
-    }
-
-    Ok(())
+    Cmd::new(opener, "explorer")
+        .arg(install_path.to_string_lossy().into_owned())
+        .spawn()
+        .map_err(|e| format!("Failed to open folder: {}", e))
 }
 
 fn get_install_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let home = dirs::home_dir().ok_or("Unable to find home directory")?;
-    Ok(home.join("claude-proxy"))
+    installer::get_install_directory()
 }
 
 fn set_environment_variable(name: &str, value: &str) -> Result<(), String> {
@@ -445,9 +654,12 @@ fn set_environment_variable(name: &str, value: &str) -> Result<(), String> {
     {
         use std::process::Command;
 
-        // Safety check: Never use setx for PATH variables due to 1024 character truncation risk
+        // Safety check: Never use setx for PATH variables due to 1024 character truncation risk.
+        // Route PATH through installer::add_to_path instead, which reads/writes the registry
+        // directly via PowerShell and can handle PATH values up to 32,767 characters.
         if name.to_uppercase() == "PATH" {
-            return Err("ERROR: PATH variables must be set using PowerShell to avoid truncation. Use add_to_path() function instead.".to_string());
+            return crate::installer::add_to_path(Path::new(value))
+                .map_err(|e| format!("Failed to extend PATH: {}", e));
         }
 
         // setx is safe for short values like API keys (under 1024 characters)
@@ -493,6 +705,11 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             detect_dependencies,
             start_installation,
+            update_dependencies,
+            list_providers,
+            list_profiles,
+            get_doctor_report,
+            get_log_path,
             check_existing_keys,
             save_configuration,
             launch_proxy,
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Describes one proxy target end to end: its embedded scripts, the
+/// launcher script that starts it, and the desktop shortcut that should
+/// point at that launcher. Adding a new provider (e.g. another
+/// OpenAI-compatible endpoint) means registering one descriptor here
+/// instead of touching `install_proxy_scripts`/`create_shortcuts` directly.
+#[derive(Clone)]
+pub struct Provider {
+    pub id: &'static str,
+    pub display_name: &'static str,
+    pub scripts: Vec<(&'static str, &'static str)>,
+    pub launcher_name: &'static str,
+    pub shortcut_name: &'static str,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProviderInfo {
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+}
+
+/// The full provider registry. Scripts specific to one provider live here;
+/// scripts shared by every provider (the core proxy, the setup script, the
+/// cross-platform `claudeproxy` entry point) stay in `shared_scripts`.
+pub fn all_providers() -> Vec<Provider> {
+    vec![
+        Provider {
+            id: "xai",
+            display_name: "xAI",
+            scripts: vec![
+                ("xai_claude_proxy_enhanced.py", include_str!("../../../xai_claude_proxy_enhanced.py")),
+                ("xai_adapter.py", include_str!("../../../xai_adapter.py")),
+                ("start_xai_proxy.bat", include_str!("../../../start_xai_proxy.bat")),
+            ],
+            launcher_name: "start_xai_proxy.bat",
+            shortcut_name: "xAI Claude Proxy.bat",
+        },
+        Provider {
+            id: "groq",
+            display_name: "GroqCloud",
+            scripts: vec![
+                ("groq_claude_proxy_enhanced.py", include_str!("../../../groq_claude_proxy_enhanced.py")),
+                ("groq_adapter.py", include_str!("../../../groq_adapter.py")),
+                ("start_groq_proxy.bat", include_str!("../../../start_groq_proxy.bat")),
+            ],
+            launcher_name: "start_groq_proxy.bat",
+            shortcut_name: "GroqCloud Claude Proxy.bat",
+        },
+    ]
+}
+
+/// Scripts every provider needs regardless of which ones are selected.
+pub fn shared_scripts() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("proxy_core.py", include_str!("../../../proxy_core.py")),
+        ("proxy_common.py", include_str!("../../../proxy_common.py")),
+        ("claudeproxy.bat", include_str!("../../../claudeproxy.bat")),
+        ("claudeproxy.sh", include_str!("../../../claudeproxy.sh")),
+        ("claudeproxysetup.py", include_str!("../../../claudeproxysetup.py")),
+    ]
+}
+
+pub fn list_provider_info() -> Vec<ProviderInfo> {
+    all_providers()
+        .into_iter()
+        .map(|p| ProviderInfo { id: p.id.to_string(), display_name: p.display_name.to_string() })
+        .collect()
+}
+
+/// Resolves a `--provider` selection string (e.g. `"xai,groq"` or `"all"`)
+/// into the matching provider descriptors, preserving registry order.
+pub fn resolve_selection(selection: &str) -> Vec<Provider> {
+    if selection.trim().eq_ignore_ascii_case("all") || selection.trim().is_empty() {
+        return all_providers();
+    }
+
+    let wanted: Vec<String> = selection.split(',').map(|s| s.trim().to_lowercase()).collect();
+    all_providers().into_iter().filter(|p| wanted.contains(&p.id.to_lowercase())).collect()
+}
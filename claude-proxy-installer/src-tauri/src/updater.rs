@@ -0,0 +1,59 @@
+use std::process::Command;
+
+use crate::dependency_detector::DependencyStatus;
+
+fn run(program: &str, args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let status = Command::new(program).args(args).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`{} {}` exited with {}", program, args.join(" "), status).into())
+    }
+}
+
+/// Mirrors `start_installation`'s per-tool step list, but for tools that are
+/// already present and just need bringing up to date - this backs the UI's
+/// separate "Check for updates" action, distinct from first-time install.
+pub async fn update_python() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Updating Python...");
+    if cfg!(target_os = "macos") {
+        run("brew", &["upgrade", "python3"])
+    } else if cfg!(target_os = "linux") {
+        run("apt", &["install", "-y", "--only-upgrade", "python3"])
+    } else {
+        run("winget", &["upgrade", "--silent", "--id", "Python.Python.3"])
+    }
+}
+
+pub async fn update_nodejs() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Updating Node.js...");
+    if cfg!(target_os = "macos") {
+        run("brew", &["upgrade", "node"])
+    } else if cfg!(target_os = "linux") {
+        run("apt", &["install", "-y", "--only-upgrade", "nodejs"])
+    } else {
+        run("winget", &["upgrade", "--silent", "--id", "OpenJS.NodeJS"])
+    }
+}
+
+pub async fn update_git() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Updating Git...");
+    if cfg!(target_os = "macos") {
+        run("brew", &["upgrade", "git"])
+    } else if cfg!(target_os = "linux") {
+        run("apt", &["install", "-y", "--only-upgrade", "git"])
+    } else {
+        run("winget", &["upgrade", "--silent", "--id", "Git.Git"])
+    }
+}
+
+pub async fn update_claude_cli() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Updating Claude Code...");
+    run("npm", &["install", "-g", "@anthropics/claude-code@latest"])
+}
+
+/// Whether a detected dependency is worth updating at all - there's nothing
+/// to upgrade if it was never found in the first place.
+pub fn is_present(status: &DependencyStatus) -> bool {
+    !matches!(status, DependencyStatus::NotFound)
+}